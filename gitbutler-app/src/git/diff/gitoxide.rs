@@ -0,0 +1,180 @@
+//! Pure-Rust diff backend built on [gitoxide](https://github.com/Byron/gitoxide).
+//!
+//! This mirrors the libgit2-backed path in [`super`] but performs blob and
+//! tree diffing through `gix` so that diffing, blob lookups and patch
+//! generation no longer pull in the C library. It is compiled only when the
+//! `gitoxide` feature is enabled; the libgit2 path remains the default until
+//! the backend has been validated byte-for-byte against it on a corpus of
+//! test repositories.
+//!
+//! The backend deliberately does *not* reimplement header math or context
+//! handling: it produces raw hunk bodies plus the pre-image line slice and
+//! feeds them through
+//! [`crate::virtual_branches::context::merge_hunks_with_context`], so the
+//! reconstruction logic stays shared with the libgit2 path and the two
+//! backends cannot drift.
+//!
+//! Registration: the parent `git::diff` module gates this backend with
+//! `#[cfg(feature = "gitoxide")] mod gitoxide;`, and `Cargo.toml` declares the
+//! `gitoxide = ["dep:gix"]` feature (off by default) so the libgit2 path stays
+//! the default until the byte-identical validation has run against a corpus
+//! of repositories.
+
+use anyhow::Result;
+use gix::bstr::ByteSlice;
+
+use super::Hunk;
+use crate::virtual_branches::context::{merge_hunks_with_context, RawHunk};
+
+/// Diff two blobs and return hunks reconstructed through the shared
+/// [`merge_hunks_with_context`] path.
+///
+/// `old` and `new` are the full blob contents; `context_lines` matches the
+/// value the libgit2 path is configured with so both backends emit identical
+/// unified diffs.
+pub fn hunks_between_blobs(
+    old: &[u8],
+    new: &[u8],
+    context_lines: usize,
+) -> Result<Vec<Hunk>> {
+    // Binary blobs never get a line-oriented diff, exactly as libgit2 reports.
+    if is_binary(old) || is_binary(new) {
+        return Ok(vec![Hunk::binary()]);
+    }
+
+    // A blob that is not valid UTF-8 is reported as binary too, even without
+    // an early NUL, rather than failing the whole diff.
+    let (Ok(old_text), Ok(new_text)) = (old.to_str(), new.to_str()) else {
+        return Ok(vec![Hunk::binary()]);
+    };
+
+    let file_lines_before = old_text.lines().collect::<Vec<_>>();
+    let old_ends_with_newline = old_text.ends_with('\n');
+
+    // Drive gitoxide's (imara-diff backed) line interner and collect the raw
+    // change regions. Each region becomes a `RawHunk` with a minimal
+    // `@@ -a +b @@` header; the shared path then grows the context windows,
+    // coalesces neighbours and fixes up the header math.
+    let input = gix::diff::blob::intern::InternedInput::new(old_text, new_text);
+    let raw = gix::diff::blob::diff(
+        gix::diff::blob::Algorithm::Myers,
+        &input,
+        RawHunkSink::new(&input),
+    );
+
+    merge_hunks_with_context(
+        &raw,
+        context_lines,
+        &file_lines_before,
+        old_ends_with_newline,
+    )
+}
+
+/// A [`gix::diff::blob::Sink`] that turns each contiguous change region into a
+/// [`RawHunk`] carrying a bare unified-diff body.
+struct RawHunkSink<'a> {
+    input: &'a gix::diff::blob::intern::InternedInput<&'a str>,
+    hunks: Vec<RawHunk>,
+}
+
+impl<'a> RawHunkSink<'a> {
+    fn new(input: &'a gix::diff::blob::intern::InternedInput<&'a str>) -> Self {
+        Self {
+            input,
+            hunks: Vec::new(),
+        }
+    }
+
+    fn token(&self, id: gix::diff::blob::intern::Token) -> &str {
+        self.input.interner[id]
+    }
+}
+
+impl gix::diff::blob::Sink for RawHunkSink<'_> {
+    type Out = Vec<RawHunk>;
+
+    fn process_change(&mut self, before: std::ops::Range<u32>, after: std::ops::Range<u32>) {
+        let start_line = (before.start as usize) + 1;
+
+        let mut diff = format!("@@ -{} +{} @@\n", start_line, after.start + 1);
+        for &id in &self.input.before[before.start as usize..before.end as usize] {
+            diff.push('-');
+            diff.push_str(self.token(id));
+            diff.push('\n');
+        }
+        for &id in &self.input.after[after.start as usize..after.end as usize] {
+            diff.push('+');
+            diff.push_str(self.token(id));
+            diff.push('\n');
+        }
+
+        self.hunks.push(RawHunk {
+            diff,
+            start_line,
+            is_binary: false,
+        });
+    }
+
+    fn finish(self) -> Self::Out {
+        self.hunks
+    }
+}
+
+/// Match libgit2's binary heuristic: a NUL byte in the first 8000 bytes.
+fn is_binary(blob: &[u8]) -> bool {
+    blob.iter().take(8000).any(|&b| b == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A small `[package]` manifest used as the pre-image, mirroring the fixture
+    // the libgit2-backed `context` tests diff against so the two backends can be
+    // compared directly.
+    const BEFORE: &str = "[package]\nname = \"gitbutler-core\"\nversion = \"0.0.0\"\n";
+
+    #[test]
+    fn replace_line_matches_reconstructed_header() {
+        let after = "[package]\nname = \"GITBUTLER-CORE\"\nversion = \"0.0.0\"\n";
+        let hunks = hunks_between_blobs(BEFORE.as_bytes(), after.as_bytes(), 3).unwrap();
+        assert_eq!(hunks.len(), 1);
+        assert_eq!(
+            hunks[0].diff,
+            "@@ -1,3 +1,3 @@\n [package]\n-name = \"gitbutler-core\"\n+name = \"GITBUTLER-CORE\"\n version = \"0.0.0\"\n"
+        );
+    }
+
+    #[test]
+    fn missing_trailing_newline_is_honored() {
+        // Pre- and post-image both lack a trailing newline: the backend must
+        // reproduce git's `\ No newline at end of file` markers byte-for-byte,
+        // which is the whole point of threading the flag through.
+        let before = "[package]\nversion = \"0.0.0\"";
+        let after = "[package]\nversion = \"0.0.1\"";
+        let hunks = hunks_between_blobs(before.as_bytes(), after.as_bytes(), 3).unwrap();
+        assert_eq!(hunks.len(), 1);
+        assert!(hunks[0].diff.contains("\\ No newline at end of file"));
+        assert!(hunks[0].diff.ends_with("\\ No newline at end of file\n"));
+    }
+
+    #[test]
+    fn non_utf8_without_nul_falls_back_to_binary() {
+        // Latin-1 bytes: no NUL in the first 8000 bytes, but not valid UTF-8.
+        // The backend reports this as binary instead of returning an error.
+        let before = b"caf\xe9 au lait\n";
+        let after = b"caf\xe9 noir\n";
+        let hunks = hunks_between_blobs(before, after, 3).unwrap();
+        assert_eq!(hunks.len(), 1);
+        assert!(hunks[0].binary);
+    }
+
+    #[test]
+    fn binary_blob_reports_single_binary_hunk() {
+        let before = b"\0\x01\x02";
+        let after = b"\0\x03\x04";
+        let hunks = hunks_between_blobs(before, after, 3).unwrap();
+        assert_eq!(hunks.len(), 1);
+        assert!(hunks[0].binary);
+    }
+}
@@ -1,3 +1,5 @@
+use std::ops::Range;
+
 use crate::git::diff;
 use anyhow::{Context, Result};
 
@@ -7,6 +9,7 @@ pub fn hunk_with_context(
     is_binary: bool,
     context_lines: usize,
     file_lines_before: &[&str],
+    file_ends_with_newline: bool,
 ) -> Result<diff::Hunk> {
     let diff_lines = hunk_diff
         .lines()
@@ -55,6 +58,15 @@ pub fn hunk_with_context(
     let header = &diff_lines[0];
     let body = &diff_lines[1..];
 
+    // Compute word-level highlight ranges by pairing adjacent runs of removed
+    // lines with the following run of added lines. Skipped for binary hunks,
+    // where the body is not line-oriented text.
+    let (removed_highlights, added_highlights) = if is_binary {
+        (Vec::new(), Vec::new())
+    } else {
+        intra_line_highlights(body)
+    };
+
     // Update unidiff header values
     let header = header
         .split(|c| c == ' ' || c == '@')
@@ -85,6 +97,20 @@ pub fn hunk_with_context(
     let body = b;
 
     // Construct a new diff with updated header and body
+    // When the last old-side line the hunk emits is the final line of a file
+    // that does not end in a newline, git records the fact with a `\ No newline
+    // at end of file` marker. This fires whether that last line is a change or
+    // merely trailing context reaching EOF; otherwise the reconstructed patch
+    // would claim the final line ends in `\n` and `git apply` would re-add one.
+    let last_old_line = hunk_start_line + removed_count + context_after.len();
+    let touches_last_line = !file_lines_before.is_empty()
+        && last_old_line.saturating_sub(1) >= file_lines_before.len();
+    let body = if !is_binary && !file_ends_with_newline && touches_last_line {
+        with_no_newline_markers(body)
+    } else {
+        body
+    };
+
     let mut diff_lines = Vec::new();
     diff_lines.push(header);
     diff_lines.extend(body);
@@ -100,10 +126,418 @@ pub fn hunk_with_context(
         new_start: start_line_after as u32,
         new_lines: line_count_after as u32,
         binary: is_binary,
+        status: classify(removed_count, added_count),
+        removed_highlights,
+        added_highlights,
     };
     Ok(hunk)
 }
 
+/// How a hunk changes the file, mirroring the inline-diff gutter status used
+/// by editors: a pure insertion, a pure deletion, or a line modification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HunkStatus {
+    Added,
+    Removed,
+    Modified,
+}
+
+/// Classify a hunk from its pre-context `-`/`+` counts. Context lines are
+/// deliberately excluded so they cannot turn a pure addition or deletion into
+/// a spurious modification.
+fn classify(removed_count: usize, added_count: usize) -> HunkStatus {
+    match (removed_count, added_count) {
+        (0, _) => HunkStatus::Added,
+        (_, 0) => HunkStatus::Removed,
+        _ => HunkStatus::Modified,
+    }
+}
+
+/// Insert `\ No newline at end of file` markers after the last line on each
+/// side of the body. The old side is the last `-`/` ` line and the new side
+/// the last `+`/` ` line; when they coincide (a trailing context line) a
+/// single marker is emitted, exactly as git does.
+fn with_no_newline_markers(body: Vec<String>) -> Vec<String> {
+    const MARKER: &str = "\\ No newline at end of file";
+    let last_old = body.iter().rposition(|l| !l.starts_with('+'));
+    let last_new = body.iter().rposition(|l| !l.starts_with('-'));
+
+    // Insert from the higher index first so earlier indices stay valid.
+    let mut inserts: Vec<usize> = vec![last_old, last_new].into_iter().flatten().collect();
+    inserts.sort_unstable();
+    inserts.dedup();
+
+    let mut out = body;
+    for idx in inserts.into_iter().rev() {
+        out.insert(idx + 1, MARKER.to_string());
+    }
+    out
+}
+
+/// A diff hunk straight from the git backend, before surrounding context
+/// lines have been materialized. `start_line` is the 1-based line in the
+/// pre-image where the changed region begins (the same value
+/// [`hunk_with_context`] takes as `hunk_start_line`).
+pub struct RawHunk {
+    pub diff: String,
+    pub start_line: usize,
+    pub is_binary: bool,
+}
+
+/// Reconstruct hunks with surrounding context, fusing any whose context
+/// windows would touch or overlap. This mirrors git's own coalescing rule:
+/// the raw hunks are ordered by their pre-image start line and, whenever the
+/// gap between one changed region and the next is no larger than the combined
+/// `context_lines` on either side, they are emitted as a single hunk with the
+/// intervening unchanged lines materialized as ` ` context. A lone hunk falls
+/// through to the [`hunk_with_context`] base case unchanged.
+pub fn merge_hunks_with_context(
+    hunks: &[RawHunk],
+    context_lines: usize,
+    file_lines_before: &[&str],
+    file_ends_with_newline: bool,
+) -> Result<Vec<diff::Hunk>> {
+    let mut order: Vec<&RawHunk> = hunks.iter().collect();
+    order.sort_by_key(|h| h.start_line);
+
+    let mut result = Vec::new();
+    let mut group: Vec<&RawHunk> = Vec::new();
+    for h in order {
+        if let Some(prev) = group.last() {
+            let prev_end = prev.start_line + removed_count(&prev.diff);
+            let gap = h.start_line.saturating_sub(prev_end);
+            if gap > 2 * context_lines {
+                result.push(materialize(
+                    &group,
+                    context_lines,
+                    file_lines_before,
+                    file_ends_with_newline,
+                )?);
+                group.clear();
+            }
+        }
+        group.push(h);
+    }
+    if !group.is_empty() {
+        result.push(materialize(
+            &group,
+            context_lines,
+            file_lines_before,
+            file_ends_with_newline,
+        )?);
+    }
+    Ok(result)
+}
+
+/// Emit one [`diff::Hunk`] for a group of raw hunks already known to coalesce.
+fn materialize(
+    group: &[&RawHunk],
+    context_lines: usize,
+    file_lines_before: &[&str],
+    file_ends_with_newline: bool,
+) -> Result<diff::Hunk> {
+    // Base case: a single hunk is exactly what `hunk_with_context` handles.
+    if let [only] = group {
+        return hunk_with_context(
+            &only.diff,
+            only.start_line,
+            only.is_binary,
+            context_lines,
+            file_lines_before,
+            file_ends_with_newline,
+        );
+    }
+
+    let first = group[0];
+    let last = group[group.len() - 1];
+    let (_, first_new_start) = parse_header_starts(first.diff.lines().next().unwrap_or(""))?;
+
+    let context_before = context_before(first.start_line, context_lines, file_lines_before);
+    let last_end = last.start_line + removed_count(&last.diff);
+    let context_after = context_after(last_end - 1, context_lines, file_lines_before);
+
+    // Stitch the change bodies together, filling the gaps between consecutive
+    // hunks with the unchanged pre-image lines rendered as ` ` context.
+    let mut body = Vec::new();
+    body.extend(context_before.iter().cloned());
+    for (i, h) in group.iter().enumerate() {
+        body.extend(h.diff.lines().skip(1).map(std::string::ToString::to_string));
+        if let Some(next) = group.get(i + 1) {
+            let gap_start = h.start_line + removed_count(&h.diff);
+            for line in gap_start..next.start_line {
+                if let Some(l) = file_lines_before.get(line - 1) {
+                    body.push(format!(" {l}"));
+                }
+            }
+        }
+    }
+    body.extend(context_after.iter().cloned());
+
+    // Canonical unified-diff line counts: a ` `/`-` line counts on the old
+    // side, a ` `/`+` line on the new side.
+    let old_lines = body.iter().filter(|l| !l.starts_with('+')).count();
+    let new_lines = body.iter().filter(|l| !l.starts_with('-')).count();
+    let old_start = first.start_line.saturating_sub(context_before.len());
+    let new_start = first_new_start.saturating_sub(context_before.len());
+
+    let header = format!("@@ -{old_start},{old_lines} +{new_start},{new_lines} @@");
+
+    let (removed_highlights, added_highlights) = intra_line_highlights(&body);
+
+    let removed_total = body.iter().filter(|l| l.starts_with('-')).count();
+    let added_total = body.iter().filter(|l| l.starts_with('+')).count();
+
+    // As in the single-hunk path, a merged tail that reaches the last line of
+    // a newline-less file needs the `\ No newline at end of file` marker so the
+    // reconstructed patch doesn't reintroduce a trailing newline. Inserted
+    // after the line counts are computed so the markers don't skew them.
+    let touches_last_line =
+        (last_end + context_after.len()).saturating_sub(1) >= file_lines_before.len();
+    let body = if !file_ends_with_newline && touches_last_line {
+        with_no_newline_markers(body)
+    } else {
+        body
+    };
+
+    let mut diff_lines = vec![header];
+    diff_lines.extend(body);
+    let mut diff = diff_lines.join("\n");
+    diff.push('\n');
+
+    #[allow(clippy::cast_possible_truncation)]
+    Ok(diff::Hunk {
+        diff,
+        old_start: old_start as u32,
+        old_lines: old_lines as u32,
+        new_start: new_start as u32,
+        new_lines: new_lines as u32,
+        binary: false,
+        status: classify(removed_total, added_total),
+        removed_highlights,
+        added_highlights,
+    })
+}
+
+/// Number of `-` records in a hunk body.
+fn removed_count(diff: &str) -> usize {
+    diff.lines().skip(1).filter(|l| l.starts_with('-')).count()
+}
+
+/// Parse the `-old +new` start lines out of a `@@ ... @@` header.
+fn parse_header_starts(header_line: &str) -> Result<(usize, usize)> {
+    let parts = header_line
+        .split(|c| c == ' ' || c == '@')
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>();
+    let old = parts[0].split(',').next().unwrap_or("0");
+    let new = parts[1].split(',').next().unwrap_or("0");
+    let old = old
+        .parse::<isize>()
+        .context("failed to parse unidiff header value for start line before")?
+        .unsigned_abs();
+    let new = new
+        .parse::<isize>()
+        .context("failed to parse unidiff header value for start line after")?
+        .unsigned_abs();
+    Ok((old, new))
+}
+
+/// `context_lines` pre-image lines immediately before `start_line` (1-based),
+/// each prefixed with the ` ` context marker and returned in file order.
+fn context_before(
+    start_line: usize,
+    context_lines: usize,
+    file_lines_before: &[&str],
+) -> Vec<String> {
+    let mut out = Vec::new();
+    for i in 1..=context_lines {
+        if start_line > i {
+            if let Some(l) = file_lines_before.get(start_line - i - 1) {
+                out.push(format!(" {l}"));
+            }
+        }
+    }
+    out.reverse();
+    out
+}
+
+/// `context_lines` pre-image lines starting at 0-based index `start_idx`, each
+/// prefixed with the ` ` context marker.
+fn context_after(start_idx: usize, context_lines: usize, file_lines_before: &[&str]) -> Vec<String> {
+    let mut out = Vec::new();
+    for i in 0..context_lines {
+        if let Some(l) = file_lines_before.get(start_idx + i) {
+            out.push(format!(" {l}"));
+        }
+    }
+    out
+}
+
+/// Walk the hunk body and, for every adjacent `-`/`+` run pair, compute the
+/// byte ranges that actually changed on each side so the UI can bold exactly
+/// the characters that differ. The returned vectors are parallel to the
+/// removed and added lines in body order; a line with no counterpart (pure
+/// addition or deletion) contributes an empty range list.
+fn intra_line_highlights(body: &[String]) -> (Vec<Vec<Range<u32>>>, Vec<Vec<Range<u32>>>) {
+    let mut removed_highlights = Vec::new();
+    let mut added_highlights = Vec::new();
+
+    let mut i = 0;
+    while i < body.len() {
+        if body[i].starts_with('-') {
+            // Collect the run of removed lines followed by the run of added lines.
+            let removed_start = i;
+            while i < body.len() && body[i].starts_with('-') {
+                i += 1;
+            }
+            let removed = &body[removed_start..i];
+            let added_start = i;
+            while i < body.len() && body[i].starts_with('+') {
+                i += 1;
+            }
+            let added = &body[added_start..i];
+
+            // Pair lines positionally; when the runs differ in length the
+            // unmatched tail of the longer run is left unhighlighted.
+            let paired = removed.len().min(added.len());
+            for (removed_line, added_line) in removed.iter().zip(added.iter()).take(paired) {
+                let (rm, add) = changed_ranges(&removed_line[1..], &added_line[1..]);
+                removed_highlights.push(rm);
+                added_highlights.push(add);
+            }
+            for _ in paired..removed.len() {
+                removed_highlights.push(Vec::new());
+            }
+            for _ in paired..added.len() {
+                added_highlights.push(Vec::new());
+            }
+        } else if body[i].starts_with('+') {
+            // A `+` run with no preceding `-` run: pure additions.
+            added_highlights.push(Vec::new());
+            i += 1;
+        } else {
+            // Context line, carries no highlight.
+            i += 1;
+        }
+    }
+
+    (removed_highlights, added_highlights)
+}
+
+/// Split a line into tokens: runs of word characters, runs of whitespace, and
+/// each remaining punctuation character on its own. Returns `(byte_offset,
+/// token)` pairs so callers can map token indices back to byte ranges.
+fn tokenize(line: &str) -> Vec<(usize, &str)> {
+    let mut tokens = Vec::new();
+    let mut chars = line.char_indices().peekable();
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_alphanumeric() || c == '_' {
+            chars.next();
+            let mut end = start + c.len_utf8();
+            while let Some(&(idx, c)) = chars.peek() {
+                if c.is_alphanumeric() || c == '_' {
+                    end = idx + c.len_utf8();
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            tokens.push((start, &line[start..end]));
+        } else if c.is_whitespace() {
+            chars.next();
+            let mut end = start + c.len_utf8();
+            while let Some(&(idx, c)) = chars.peek() {
+                if c.is_whitespace() {
+                    end = idx + c.len_utf8();
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            tokens.push((start, &line[start..end]));
+        } else {
+            chars.next();
+            tokens.push((start, &line[start..start + c.len_utf8()]));
+        }
+    }
+    tokens
+}
+
+/// Compute the byte ranges of `a` and `b` that do not belong to their longest
+/// common subsequence of tokens. Adjacent changed tokens are coalesced into a
+/// single range.
+fn changed_ranges(a: &str, b: &str) -> (Vec<Range<u32>>, Vec<Range<u32>>) {
+    let a_tokens = tokenize(a);
+    let b_tokens = tokenize(b);
+
+    // Classic LCS dynamic programming table over the token sequences.
+    let rows = a_tokens.len();
+    let cols = b_tokens.len();
+    let mut lcs = vec![vec![0u32; cols + 1]; rows + 1];
+    for r in (0..rows).rev() {
+        for c in (0..cols).rev() {
+            lcs[r][c] = if a_tokens[r].1 == b_tokens[c].1 {
+                lcs[r + 1][c + 1] + 1
+            } else {
+                lcs[r + 1][c].max(lcs[r][c + 1])
+            };
+        }
+    }
+
+    // Backtrack: tokens not on the common subsequence are the changed spans.
+    let mut a_changed = Vec::new();
+    let mut b_changed = Vec::new();
+    let (mut r, mut c) = (0, 0);
+    while r < rows && c < cols {
+        if a_tokens[r].1 == b_tokens[c].1 {
+            r += 1;
+            c += 1;
+        } else if lcs[r + 1][c] >= lcs[r][c + 1] {
+            a_changed.push(token_range(a, &a_tokens, r));
+            r += 1;
+        } else {
+            b_changed.push(token_range(b, &b_tokens, c));
+            c += 1;
+        }
+    }
+    while r < rows {
+        a_changed.push(token_range(a, &a_tokens, r));
+        r += 1;
+    }
+    while c < cols {
+        b_changed.push(token_range(b, &b_tokens, c));
+        c += 1;
+    }
+
+    (coalesce(a_changed), coalesce(b_changed))
+}
+
+/// Byte range covered by the token at `idx` within `line`.
+fn token_range(line: &str, tokens: &[(usize, &str)], idx: usize) -> Range<u32> {
+    let (start, tok) = tokens[idx];
+    #[allow(clippy::cast_possible_truncation)]
+    Range {
+        start: start as u32,
+        end: (start + tok.len()) as u32,
+    }
+}
+
+/// Merge touching/adjacent ranges so the UI gets one highlight per run.
+fn coalesce(mut ranges: Vec<Range<u32>>) -> Vec<Range<u32>> {
+    ranges.sort_by_key(|r| r.start);
+    let mut merged: Vec<Range<u32>> = Vec::new();
+    for r in ranges {
+        if let Some(last) = merged.last_mut() {
+            if r.start <= last.end {
+                last.end = last.end.max(r.end);
+                continue;
+            }
+        }
+        merged.push(r);
+    }
+    merged
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -113,7 +547,7 @@ mod tests {
 -serde = ["dep:serde", "uuid/serde"]
 +SERDE = ["dep:serde", "uuid/serde"]
 "#;
-        let with_ctx = hunk_with_context(hunk_diff, 8, false, 3, &file_lines()).unwrap();
+        let with_ctx = hunk_with_context(hunk_diff, 8, false, 3, &file_lines(), true).unwrap();
         assert_eq!(
             with_ctx.diff,
             r#"@@ -5,7 +5,7 @@
@@ -139,7 +573,7 @@ mod tests {
 -name = "gitbutler-core"
 +NAME = "gitbutler-core"
 "#;
-        let with_ctx = hunk_with_context(hunk_diff, 2, false, 3, &file_lines()).unwrap();
+        let with_ctx = hunk_with_context(hunk_diff, 2, false, 3, &file_lines(), true).unwrap();
         assert_eq!(
             with_ctx.diff,
             r#"@@ -1,5 +1,5 @@
@@ -163,7 +597,7 @@ mod tests {
 -[package]
 +[PACKAGE]
 ";
-        let with_ctx = hunk_with_context(hunk_diff, 1, false, 3, &file_lines()).unwrap();
+        let with_ctx = hunk_with_context(hunk_diff, 1, false, 3, &file_lines(), true).unwrap();
         assert_eq!(
             with_ctx.diff,
             r#"@@ -1,4 +1,4 @@
@@ -186,7 +620,7 @@ mod tests {
 -serde = { workspace = true, optional = true }
 +SERDE = { workspace = true, optional = true }
 ";
-        let with_ctx = hunk_with_context(hunk_diff, 13, false, 3, &file_lines()).unwrap();
+        let with_ctx = hunk_with_context(hunk_diff, 13, false, 3, &file_lines(), true).unwrap();
         assert_eq!(
             with_ctx.diff,
             r#"@@ -10,5 +10,5 @@
@@ -204,6 +638,182 @@ mod tests {
         assert_eq!(with_ctx.new_lines, 5);
     }
 
+    #[test]
+    fn word_level_highlights_single_token() {
+        let hunk_diff = r#"@@ -8 +8 @@ default = ["serde", "rusqlite"]
+-serde = ["dep:serde", "uuid/serde"]
++SERDE = ["dep:serde", "uuid/serde"]
+"#;
+        let with_ctx = hunk_with_context(hunk_diff, 8, false, 3, &file_lines(), true).unwrap();
+        assert_eq!(with_ctx.removed_highlights, vec![vec![0..5]]);
+        assert_eq!(with_ctx.added_highlights, vec![vec![0..5]]);
+    }
+
+    #[test]
+    fn word_level_highlights_skip_binary() {
+        let hunk_diff = r#"@@ -8 +8 @@
+-serde
++SERDE
+"#;
+        let with_ctx = hunk_with_context(hunk_diff, 8, true, 3, &file_lines(), true).unwrap();
+        assert!(with_ctx.removed_highlights.is_empty());
+        assert!(with_ctx.added_highlights.is_empty());
+    }
+
+    #[test]
+    fn word_level_highlights_pure_addition_is_empty() {
+        let (removed, added) = intra_line_highlights(&["+new line".to_string()]);
+        assert!(removed.is_empty());
+        assert_eq!(added, vec![Vec::<Range<u32>>::new()]);
+    }
+
+    #[test]
+    fn merge_overlapping_hunks() {
+        let hunks = vec![
+            RawHunk {
+                diff: "@@ -7 +7 @@\n-default = [\"serde\", \"rusqlite\"]\n+DEFAULT = [\"serde\", \"rusqlite\"]\n".to_string(),
+                start_line: 7,
+                is_binary: false,
+            },
+            RawHunk {
+                diff: "@@ -9 +9 @@\n-rusqlite = [\"dep:rusqlite\"]\n+RUSQLITE = [\"dep:rusqlite\"]\n".to_string(),
+                start_line: 9,
+                is_binary: false,
+            },
+        ];
+        let merged = merge_hunks_with_context(&hunks, 3, &file_lines(), true).unwrap();
+        assert_eq!(merged.len(), 1);
+        assert_eq!(
+            merged[0].diff,
+            "@@ -4,9 +4,9 @@\n edition = \"2021\"\n \n [features]\n-default = [\"serde\", \"rusqlite\"]\n+DEFAULT = [\"serde\", \"rusqlite\"]\n serde = [\"dep:serde\", \"uuid/serde\"]\n-rusqlite = [\"dep:rusqlite\"]\n+RUSQLITE = [\"dep:rusqlite\"]\n \n [dependencies]\n rusqlite = { workspace = true, optional = true }\n"
+        );
+        assert_eq!(merged[0].old_start, 4);
+        assert_eq!(merged[0].old_lines, 9);
+    }
+
+    #[test]
+    fn distant_hunks_are_not_merged() {
+        let hunks = vec![
+            RawHunk {
+                diff: "@@ -2 +2 @@\n-name = \"gitbutler-core\"\n+NAME = \"gitbutler-core\"\n".to_string(),
+                start_line: 2,
+                is_binary: false,
+            },
+            RawHunk {
+                diff: "@@ -13 +13 @@\n-serde = { workspace = true, optional = true }\n+SERDE = { workspace = true, optional = true }\n".to_string(),
+                start_line: 13,
+                is_binary: false,
+            },
+        ];
+        let merged = merge_hunks_with_context(&hunks, 3, &file_lines(), true).unwrap();
+        assert_eq!(merged.len(), 2);
+    }
+
+    #[test]
+    fn status_modified_for_replaced_line() {
+        let hunk_diff = "@@ -8 +8 @@\n-serde\n+SERDE\n";
+        let with_ctx = hunk_with_context(hunk_diff, 8, false, 3, &file_lines(), true).unwrap();
+        assert_eq!(with_ctx.status, HunkStatus::Modified);
+    }
+
+    #[test]
+    fn status_added_for_pure_insertion() {
+        let hunk_diff = "@@ -8,0 +9 @@\n+extra = true\n";
+        let with_ctx = hunk_with_context(hunk_diff, 9, false, 3, &file_lines(), true).unwrap();
+        assert_eq!(with_ctx.status, HunkStatus::Added);
+    }
+
+    #[test]
+    fn status_removed_for_pure_deletion() {
+        let hunk_diff = "@@ -8 +7,0 @@\n-serde = [\"dep:serde\", \"uuid/serde\"]\n";
+        let with_ctx = hunk_with_context(hunk_diff, 8, false, 3, &file_lines(), true).unwrap();
+        assert_eq!(with_ctx.status, HunkStatus::Removed);
+    }
+
+    #[test]
+    fn replace_line_bottom_file_no_newline() {
+        let hunk_diff = "@@ -14 +14 @@
+-uuid = { workspace = true, features = [\"v4\", \"fast-rng\"] }
++UUID = { workspace = true, features = [\"v4\", \"fast-rng\"] }
+";
+        let with_ctx = hunk_with_context(hunk_diff, 14, false, 3, &file_lines(), false).unwrap();
+        assert_eq!(
+            with_ctx.diff,
+            "@@ -11,4 +11,4 @@\n [dependencies]\n rusqlite = { workspace = true, optional = true }\n serde = { workspace = true, optional = true }\n-uuid = { workspace = true, features = [\"v4\", \"fast-rng\"] }\n\\ No newline at end of file\n+UUID = { workspace = true, features = [\"v4\", \"fast-rng\"] }\n\\ No newline at end of file\n"
+        );
+    }
+
+    #[test]
+    fn replace_line_bottom_file_keeps_newline() {
+        let hunk_diff = "@@ -14 +14 @@
+-uuid = { workspace = true, features = [\"v4\", \"fast-rng\"] }
++UUID = { workspace = true, features = [\"v4\", \"fast-rng\"] }
+";
+        let with_ctx = hunk_with_context(hunk_diff, 14, false, 3, &file_lines(), true).unwrap();
+        assert!(!with_ctx.diff.contains("No newline"));
+    }
+
+    #[test]
+    fn merge_overlapping_hunks_bottom_file_no_newline() {
+        let hunks = vec![
+            RawHunk {
+                diff: "@@ -12 +12 @@\n-rusqlite = { workspace = true, optional = true }\n+RUSQLITE = { workspace = true, optional = true }\n".to_string(),
+                start_line: 12,
+                is_binary: false,
+            },
+            RawHunk {
+                diff: "@@ -14 +14 @@\n-uuid = { workspace = true, features = [\"v4\", \"fast-rng\"] }\n+UUID = { workspace = true, features = [\"v4\", \"fast-rng\"] }\n".to_string(),
+                start_line: 14,
+                is_binary: false,
+            },
+        ];
+        let merged = merge_hunks_with_context(&hunks, 3, &file_lines(), false).unwrap();
+        assert_eq!(merged.len(), 1);
+        assert_eq!(
+            merged[0].diff,
+            "@@ -9,6 +9,6 @@\n rusqlite = [\"dep:rusqlite\"]\n \n [dependencies]\n-rusqlite = { workspace = true, optional = true }\n+RUSQLITE = { workspace = true, optional = true }\n serde = { workspace = true, optional = true }\n-uuid = { workspace = true, features = [\"v4\", \"fast-rng\"] }\n\\ No newline at end of file\n+UUID = { workspace = true, features = [\"v4\", \"fast-rng\"] }\n\\ No newline at end of file\n"
+        );
+    }
+
+    #[test]
+    fn trailing_context_reaching_eof_no_newline() {
+        // Change line 13; line 14 rides along as trailing context and reaches
+        // the end of a newline-less file, so the marker follows that context.
+        let hunk_diff = "@@ -13 +13 @@
+-serde = { workspace = true, optional = true }
++SERDE = { workspace = true, optional = true }
+";
+        let with_ctx = hunk_with_context(hunk_diff, 13, false, 3, &file_lines(), false).unwrap();
+        assert_eq!(
+            with_ctx.diff,
+            "@@ -10,5 +10,5 @@\n \n [dependencies]\n rusqlite = { workspace = true, optional = true }\n-serde = { workspace = true, optional = true }\n+SERDE = { workspace = true, optional = true }\n uuid = { workspace = true, features = [\"v4\", \"fast-rng\"] }\n\\ No newline at end of file\n"
+        );
+    }
+
+    #[test]
+    fn merge_trailing_context_reaching_eof_no_newline() {
+        // Two changes coalesce; the last line of the newline-less file is
+        // trailing context, not a change, yet the marker must still appear.
+        let hunks = vec![
+            RawHunk {
+                diff: "@@ -11 +11 @@\n-[dependencies]\n+[DEPENDENCIES]\n".to_string(),
+                start_line: 11,
+                is_binary: false,
+            },
+            RawHunk {
+                diff: "@@ -13 +13 @@\n-serde = { workspace = true, optional = true }\n+SERDE = { workspace = true, optional = true }\n".to_string(),
+                start_line: 13,
+                is_binary: false,
+            },
+        ];
+        let merged = merge_hunks_with_context(&hunks, 3, &file_lines(), false).unwrap();
+        assert_eq!(merged.len(), 1);
+        assert_eq!(
+            merged[0].diff,
+            "@@ -8,7 +8,7 @@\n serde = [\"dep:serde\", \"uuid/serde\"]\n rusqlite = [\"dep:rusqlite\"]\n \n-[dependencies]\n+[DEPENDENCIES]\n rusqlite = { workspace = true, optional = true }\n-serde = { workspace = true, optional = true }\n+SERDE = { workspace = true, optional = true }\n uuid = { workspace = true, features = [\"v4\", \"fast-rng\"] }\n\\ No newline at end of file\n"
+        );
+    }
+
     fn file_lines() -> Vec<&'static str> {
         let file_lines_before = r#"[package]
 name = "gitbutler-core"
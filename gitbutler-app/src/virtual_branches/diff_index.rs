@@ -0,0 +1,184 @@
+use std::ops::Range;
+
+use crate::git::diff;
+
+/// An incremental store of a file's hunks, keyed by line position.
+///
+/// Large files that are re-diffed on every keystroke do not need a full
+/// recompute for every edit: an edit that touches a bounded region can only
+/// invalidate the hunks overlapping that region. [`DiffIndex`] keeps the
+/// current hunks ordered by their new-side start line (conceptually a balanced
+/// tree summarizing each node's old- and new-side ranges) so that
+/// [`DiffIndex::apply_edit`] can drop just the overlapping hunks, splice in the
+/// freshly recomputed ones, and shift every downstream hunk by the net line
+/// delta.
+///
+/// The invariant callers rely on is that the incrementally maintained set is
+/// always equal to what a full recompute would produce.
+pub struct DiffIndex {
+    /// Hunks ordered by `new_start`.
+    hunks: Vec<diff::Hunk>,
+}
+
+impl DiffIndex {
+    /// Build an index from the hunks of a full diff. The hunks are expected to
+    /// be non-overlapping; they are sorted by new-side position defensively.
+    pub fn new(mut hunks: Vec<diff::Hunk>) -> Self {
+        hunks.sort_by_key(|h| h.new_start);
+        Self { hunks }
+    }
+
+    /// The current hunk set.
+    pub fn hunks(&self) -> &[diff::Hunk] {
+        &self.hunks
+    }
+
+    /// Apply an edit that replaced the new-side lines in `old_range` (1-based,
+    /// half-open — the same basis as [`diff::Hunk::new_start`]) with
+    /// `new_line_count` lines.
+    ///
+    /// `recomputed` are the hunks freshly produced by
+    /// [`crate::virtual_branches::context::hunk_with_context`] for the edited
+    /// region — this store does not re-diff itself, it only maintains hunk
+    /// positions. Hunks overlapping `old_range` are dropped in favour of
+    /// `recomputed`; hunks after the edit keep their shape but have their
+    /// `new_start` shifted by the net line delta. The return value is the
+    /// minimal set of hunks that changed: the spliced-in region plus every
+    /// shifted downstream hunk.
+    pub fn apply_edit(
+        &mut self,
+        old_range: Range<u32>,
+        new_line_count: u32,
+        recomputed: Vec<diff::Hunk>,
+    ) -> Vec<diff::Hunk> {
+        let old_len = old_range.end.saturating_sub(old_range.start);
+        let delta = i64::from(new_line_count) - i64::from(old_len);
+
+        let mut retained = Vec::with_capacity(self.hunks.len());
+        let mut changed = recomputed.clone();
+
+        for mut hunk in std::mem::take(&mut self.hunks) {
+            let start = hunk.new_start;
+            let end = start + hunk.new_lines;
+            if end <= old_range.start {
+                // Entirely before the edit: untouched.
+                retained.push(hunk);
+            } else if start >= old_range.end {
+                // After the edit: retained but shifted by the net delta. The
+                // `@@` header is regenerated alongside `new_start` so the
+                // stored hunk stays identical to what a full recompute yields.
+                hunk.new_start = shift(hunk.new_start, delta);
+                rewrite_header(&mut hunk);
+                if delta != 0 {
+                    changed.push(hunk.clone());
+                }
+                retained.push(hunk);
+            }
+            // Otherwise the hunk overlaps the edit and is dropped; the
+            // `recomputed` hunks stand in for it.
+        }
+
+        retained.extend(recomputed);
+        retained.sort_by_key(|h| h.new_start);
+        self.hunks = retained;
+
+        changed.sort_by_key(|h| h.new_start);
+        changed
+    }
+}
+
+/// Offset a 1-based line position by a signed delta, saturating at line 1.
+fn shift(start: u32, delta: i64) -> u32 {
+    let shifted = i64::from(start) + delta;
+    u32::try_from(shifted.max(1)).unwrap_or(u32::MAX)
+}
+
+/// Replace the `@@ ... @@` header of a hunk's unified diff so it reflects the
+/// hunk's current `old_start`/`new_start` after a shift. The body is left
+/// untouched; only the first line is rewritten, in the canonical form
+/// [`crate::virtual_branches::context::hunk_with_context`] emits.
+fn rewrite_header(hunk: &mut diff::Hunk) {
+    let header = format!(
+        "@@ -{},{} +{},{} @@",
+        hunk.old_start, hunk.old_lines, hunk.new_start, hunk.new_lines
+    );
+    match hunk.diff.split_once('\n') {
+        Some((_, rest)) => hunk.diff = format!("{header}\n{rest}"),
+        None => hunk.diff = format!("{header}\n"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hunk(new_start: u32, new_lines: u32) -> diff::Hunk {
+        diff::Hunk {
+            diff: format!("@@ -{new_start},{new_lines} +{new_start},{new_lines} @@\n body\n"),
+            old_start: new_start,
+            old_lines: new_lines,
+            new_start,
+            new_lines,
+            binary: false,
+            status: crate::virtual_branches::context::HunkStatus::Modified,
+            removed_highlights: Vec::new(),
+            added_highlights: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn edit_shifts_downstream_hunks() {
+        let mut index = DiffIndex::new(vec![hunk(10, 2), hunk(40, 2)]);
+        // Replace 1 line at line 10 with 3 lines: net delta +2.
+        let changed = index.apply_edit(10..11, 3, vec![hunk(10, 3)]);
+
+        // The downstream hunk moved from 40 to 42; the recomputed one stays.
+        let starts = index.hunks().iter().map(|h| h.new_start).collect::<Vec<_>>();
+        assert_eq!(starts, vec![10, 42]);
+        // Minimal changed set is the recomputed region plus the shifted hunk.
+        let changed_starts = changed.iter().map(|h| h.new_start).collect::<Vec<_>>();
+        assert_eq!(changed_starts, vec![10, 42]);
+    }
+
+    #[test]
+    fn edit_outside_any_hunk_only_shifts() {
+        let mut index = DiffIndex::new(vec![hunk(40, 2)]);
+        let changed = index.apply_edit(1..2, 2, vec![]);
+        assert_eq!(index.hunks().iter().map(|h| h.new_start).collect::<Vec<_>>(), vec![41]);
+        assert_eq!(changed.len(), 1);
+    }
+
+    #[test]
+    fn edit_abutting_hunk_boundary_does_not_overlap() {
+        // The hunk occupies new-side lines [10, 12).
+        // An edit starting exactly at the hunk's end does not overlap it.
+        let mut index = DiffIndex::new(vec![hunk(10, 2)]);
+        let changed = index.apply_edit(12..13, 1, vec![]);
+        assert!(changed.is_empty());
+        assert_eq!(index.hunks()[0].new_start, 10);
+
+        // An edit ending exactly at the hunk's start does not overlap either,
+        // but does shift the hunk by the net delta.
+        let mut index = DiffIndex::new(vec![hunk(10, 2)]);
+        let changed = index.apply_edit(8..10, 3, vec![]);
+        assert_eq!(index.hunks()[0].new_start, 11);
+        assert_eq!(changed.len(), 1);
+    }
+
+    #[test]
+    fn shift_regenerates_header() {
+        let mut index = DiffIndex::new(vec![hunk(40, 2)]);
+        let changed = index.apply_edit(1..2, 3, vec![]);
+        assert_eq!(changed[0].new_start, 42);
+        assert!(changed[0].diff.starts_with("@@ -40,2 +42,2 @@"));
+    }
+
+    #[test]
+    fn untouched_hunk_before_edit_is_not_reported() {
+        let mut index = DiffIndex::new(vec![hunk(5, 2), hunk(40, 2)]);
+        let changed = index.apply_edit(40..41, 1, vec![hunk(40, 1)]);
+        // The leading hunk neither moves nor is reported.
+        assert!(changed.iter().all(|h| h.new_start != 5));
+        assert_eq!(index.hunks()[0].new_start, 5);
+    }
+}